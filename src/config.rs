@@ -0,0 +1,57 @@
+use serde::Deserialize;
+use std::path::Path;
+
+/// A tier of procedurally generated perks, e.g. the 40 Red Giants or 300 Stars.
+/// Expanded into `count` individual `PerkPoint`s named `"{name_prefix} {n}"`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PerkTier {
+    pub name_prefix: String,
+    pub count: usize,
+    pub cost: f32,
+    pub min_r_percent: f32,
+}
+
+/// A single hand-placed perk, e.g. one of the fixed supernovas.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NamedPerk {
+    pub name: String,
+    pub description: String,
+    pub angle_deg: f32,
+    pub radius_val: f32,
+    pub cost: f32,
+    /// Names of other named perks that must be unlocked before this one counts,
+    /// resolved to perk indices when the tree is built.
+    #[serde(default)]
+    pub requires: Vec<String>,
+}
+
+/// On-disk description of the whole skill tree: class/tier generation
+/// parameters plus any explicitly placed perks. Deserialized from a RON
+/// file at startup so the tree can be tuned without recompiling.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PerkConfig {
+    #[serde(default)]
+    pub tiers: Vec<PerkTier>,
+    #[serde(default)]
+    pub named_perks: Vec<NamedPerk>,
+}
+
+/// Where `StatApp` looks for a perk config, relative to the working directory.
+pub const CONFIG_PATH: &str = "perks.ron";
+
+impl PerkConfig {
+    /// Loads the perk config from `path`. `Ok(None)` means no file is
+    /// present, so the caller should fall back to the hardcoded set. `Err`
+    /// means a file is present but can't be read or isn't valid RON, which the caller should
+    /// surface to the user rather than silently falling back or ignoring.
+    pub fn load_from_file(path: &Path) -> Result<Option<Self>, String> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| format!("Couldn't read {:?}: {err}", path))?;
+        ron::from_str::<Self>(&contents)
+            .map(Some)
+            .map_err(|err| format!("Couldn't parse {:?}: {err}", path))
+    }
+}
@@ -1,12 +1,43 @@
+mod config;
+
+use base64::Engine;
+use config::{NamedPerk, PerkConfig, PerkTier};
 use eframe::egui;
 use egui::{Color32, Pos2, Shape, Stroke};
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::f32::consts::PI;
+use std::path::Path;
+use std::sync::mpsc::Receiver;
 
 const MAX_STAT_VAL: f32 = 100.0;
 const MIN_STAT_VAL: f32 = 10.0;
 const MAX_TOTAL_POINTS: f32 = 120.0;
 
+// Genetic algorithm tuning for the "Optimize Build" search
+const GA_POPULATION: usize = 200;
+const GA_GENERATIONS: usize = 50;
+const GA_ELITE_FRACTION: f32 = 0.2;
+const GA_MUTATION_STD: f32 = 8.0;
+
+// Prerequisite links are flattened by recursively splitting the cubic Bezier
+// at its midpoint (de Casteljau) this many times: 2^4 = 16 segments.
+const BEZIER_SUBDIVISION_DEPTH: u32 = 4;
+
+// Post-generation repulsion pass so randomly placed perks don't pile on top
+// of each other. Threshold/strength are in the same unit space as
+// `radius_val` (0..=MAX_STAT_VAL), not screen pixels.
+const RELAX_ITERATIONS: usize = 30;
+const RELAX_NEIGHBOR_THRESHOLD: f32 = 6.0;
+const RELAX_REPULSION_STRENGTH: f32 = 0.5;
+
+// HUD ring showing the STR/DEX/INT point split, fixed to the viewport so it
+// doesn't move or scale with pan/zoom.
+const RING_SCREEN_RADIUS: f32 = 70.0;
+const RING_THICKNESS: f32 = 14.0;
+const RING_ANIM_LERP: f32 = 0.12;
+const RING_ANIM_EPSILON: f32 = 0.001;
+
 // Angles (radians)
 const ANG_STR_RED: f32 = 135.0 * (PI / 180.0);
 const ANG_DEX_GREEN: f32 = 45.0 * (PI / 180.0);
@@ -19,6 +50,78 @@ struct PerkPoint {
     angle: f32,
     radius_val: f32,
     cost: f32,
+    wanted: bool,
+    // Indices into the owning `Vec<PerkPoint>` that must also be unlocked.
+    requires: Vec<usize>,
+}
+
+// One row of the convergence log shown under the "Optimize Build" button
+struct GenerationStats {
+    generation: usize,
+    max: f32,
+    mean: f32,
+    median: f32,
+    min: f32,
+}
+
+impl GenerationStats {
+    // `fitnesses` must already be sorted descending (what the GA loop produces)
+    fn from_sorted_desc(generation: usize, fitnesses: &[f32]) -> Self {
+        let n = fitnesses.len();
+        let median = if n % 2 == 0 {
+            (fitnesses[n / 2 - 1] + fitnesses[n / 2]) / 2.0
+        } else {
+            fitnesses[n / 2]
+        };
+        Self {
+            generation,
+            max: fitnesses[0],
+            mean: fitnesses.iter().sum::<f32>() / n as f32,
+            median,
+            min: fitnesses[n - 1],
+        }
+    }
+}
+
+// Everything needed to reproduce a build elsewhere: the three stats plus
+// view state and the perks the player flagged, addressed by name so a
+// code still imports after perks are reordered or added; a code naming a
+// perk that no longer exists is rejected on import.
+#[derive(Serialize, Deserialize)]
+struct SavedBuild {
+    strength: f32,
+    dexterity: f32,
+    intelligence: f32,
+    zoom: f32,
+    offset_x: f32,
+    offset_y: f32,
+    wanted: Vec<String>,
+}
+
+// Watches the on-disk perk config so edits hot-reload without a restart.
+struct PerkConfigWatcher {
+    _watcher: notify::RecommendedWatcher,
+    rx: Receiver<notify::Result<notify::Event>>,
+}
+
+impl PerkConfigWatcher {
+    fn spawn(path: &Path) -> Option<Self> {
+        use notify::{RecursiveMode, Watcher};
+
+        if !path.exists() {
+            return None;
+        }
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |res| {
+            let _ = tx.send(res);
+        })
+        .ok()?;
+        watcher.watch(path, RecursiveMode::NonRecursive).ok()?;
+        Some(Self {
+            _watcher: watcher,
+            rx,
+        })
+    }
 }
 
 struct StatApp {
@@ -28,6 +131,19 @@ struct StatApp {
     zoom: f32,
     offset: egui::Vec2,
     perks: Vec<PerkPoint>,
+    optimizer_log: Vec<GenerationStats>,
+    config_watch: Option<PerkConfigWatcher>,
+    // Currently displayed fractions (0..1 of MAX_TOTAL_POINTS) for the HUD
+    // ring, eased toward the real stat fractions each frame.
+    ring_str_anim: f32,
+    ring_dex_anim: f32,
+    ring_int_anim: f32,
+    // Result of the last Export/Import attempt, shown under the buttons.
+    // `bool` is whether it's an error (for coloring).
+    build_code_status: Option<(String, bool)>,
+    // Set when `perks.ron` is present but failed to parse, so a bad edit is
+    // surfaced in the UI instead of silently falling back/no-op'ing.
+    config_error: Option<String>,
 }
 
 impl StatApp {
@@ -41,77 +157,317 @@ impl StatApp {
             (v1 * v2) / ((v2 * phi.cos()).powi(2) + (v1 * phi.sin()).powi(2)).sqrt()
         }
     }
-}
 
-impl Default for StatApp {
-    fn default() -> Self {
-        let mut perks = Vec::new();
-        let mut rng = rand::thread_rng();
+    // Same sector lookup as `get_current_radius_at_angle`, but takes the stats
+    // explicitly so the GA can probe candidate genomes without mutating `self`.
+    fn radius_at_angle_for_stats(strength: f32, dexterity: f32, intelligence: f32, angle_rad: f32) -> f32 {
+        let angle_deg = angle_rad.to_degrees().rem_euclid(360.0);
 
-        // --- A. Fixed SUPERNOVAS (Cost 10.0) ---
-        let fixed_supernovas = vec![
+        let (v1, v2, t_sector) = if (45.0..135.0).contains(&angle_deg) {
+            let t = (angle_rad - ANG_DEX_GREEN) / (ANG_STR_RED - ANG_DEX_GREEN);
+            (dexterity, strength, t)
+        } else if (135.0..270.0).contains(&angle_deg) {
+            let t = (angle_rad - ANG_STR_RED) / (ANG_INT_BLUE - ANG_STR_RED);
+            (strength, intelligence, t)
+        } else {
+            let start = ANG_INT_BLUE;
+            let end = ANG_DEX_GREEN + 2.0 * PI;
+            let curr = if angle_rad < ANG_INT_BLUE {
+                angle_rad + 2.0 * PI
+            } else {
+                angle_rad
+            };
+            let t = (curr - start) / (end - start);
+            (intelligence, dexterity, t)
+        };
+
+        Self::calculate_ellipse_radius(v1, v2, t_sector)
+    }
+
+    // The farthest radius reachable at `angle_rad` under the 120-point cap:
+    // the linear approximation used when scattering generated perks, where
+    // the 90 spare points are split between the sector's two stats in
+    // proportion to `t` (all to the start stat at t=0, 45/45 at t=0.5).
+    fn max_reachable_radius_at_angle(angle_rad: f32) -> f32 {
+        let angle_rad = angle_rad.rem_euclid(2.0 * PI);
+        let angle_deg = angle_rad.to_degrees();
+
+        let t = if (45.0..135.0).contains(&angle_deg) {
+            (angle_rad - ANG_DEX_GREEN) / (ANG_STR_RED - ANG_DEX_GREEN)
+        } else if (135.0..270.0).contains(&angle_deg) {
+            (angle_rad - ANG_STR_RED) / (ANG_INT_BLUE - ANG_STR_RED)
+        } else {
+            let start = ANG_INT_BLUE;
+            let end = ANG_DEX_GREEN + 2.0 * PI;
+            let curr = if angle_rad < ANG_INT_BLUE {
+                angle_rad + 2.0 * PI
+            } else {
+                angle_rad
+            };
+            (curr - start) / (end - start)
+        }
+        .clamp(0.0, 1.0);
+
+        let max_v1 = 100.0 - (90.0 * t);
+        let max_v2 = 10.0 + (90.0 * t);
+        Self::calculate_ellipse_radius(max_v1, max_v2, t)
+    }
+
+    // Push apart any generated perks (everything in `perks` from `start_idx`
+    // onward) that landed within `RELAX_NEIGHBOR_THRESHOLD` of each other,
+    // then re-project each back inside its sector's reachable boundary so it
+    // stays legal under the 120-point cap.
+    fn relax_generated_perks(perks: &mut [PerkPoint], start_idx: usize) {
+        let generated = &mut perks[start_idx..];
+
+        for _ in 0..RELAX_ITERATIONS {
+            let positions: Vec<(f32, f32)> = generated
+                .iter()
+                .map(|p| (p.radius_val * p.angle.cos(), p.radius_val * p.angle.sin()))
+                .collect();
+            let mut deltas = vec![(0.0f32, 0.0f32); generated.len()];
+
+            for i in 0..positions.len() {
+                for j in (i + 1)..positions.len() {
+                    let (xi, yi) = positions[i];
+                    let (xj, yj) = positions[j];
+                    let dx = xi - xj;
+                    let dy = yi - yj;
+                    let dist = (dx * dx + dy * dy).sqrt();
+                    if dist > 1e-4 && dist < RELAX_NEIGHBOR_THRESHOLD {
+                        let push = (RELAX_NEIGHBOR_THRESHOLD - dist) * RELAX_REPULSION_STRENGTH;
+                        let (nx, ny) = (dx / dist, dy / dist);
+                        deltas[i].0 += nx * push;
+                        deltas[i].1 += ny * push;
+                        deltas[j].0 -= nx * push;
+                        deltas[j].1 -= ny * push;
+                    }
+                }
+            }
+
+            for (i, perk) in generated.iter_mut().enumerate() {
+                let (x, y) = positions[i];
+                let (nx, ny) = (x + deltas[i].0, y + deltas[i].1);
+                let new_angle = ny.atan2(nx).rem_euclid(2.0 * PI);
+                let new_radius = (nx * nx + ny * ny).sqrt();
+
+                perk.angle = new_angle;
+                perk.radius_val = new_radius.min(Self::max_reachable_radius_at_angle(new_angle));
+            }
+        }
+    }
+
+    // Whether perk `idx` is unlocked: its own radius condition must hold AND
+    // every perk it requires must (recursively) be unlocked. Memoized in
+    // `cache` since prerequisite chains are walked once per perk per frame.
+    // An in-progress node is reported as locked, which also guards against
+    // any accidental cycle in `requires`.
+    fn is_unlocked_at(
+        perks: &[PerkPoint],
+        idx: usize,
+        strength: f32,
+        dexterity: f32,
+        intelligence: f32,
+        cache: &mut [Option<bool>],
+    ) -> bool {
+        if let Some(cached) = cache[idx] {
+            return cached;
+        }
+        cache[idx] = Some(false);
+
+        let perk = &perks[idx];
+        let radius_ok = perk.radius_val
+            <= Self::radius_at_angle_for_stats(strength, dexterity, intelligence, perk.angle) + 0.5;
+        let prereqs_ok = perk.requires.iter().all(|&req| {
+            Self::is_unlocked_at(perks, req, strength, dexterity, intelligence, cache)
+        });
+
+        let unlocked = radius_ok && prereqs_ok;
+        cache[idx] = Some(unlocked);
+        unlocked
+    }
+
+    fn lerp_pos2(a: Pos2, b: Pos2, t: f32) -> Pos2 {
+        Pos2::new(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t)
+    }
+
+    // One step of de Casteljau's algorithm: splits a cubic Bezier at t=0.5
+    // into two cubic Beziers that together trace the same curve.
+    fn split_bezier(p0: Pos2, p1: Pos2, p2: Pos2, p3: Pos2) -> ([Pos2; 4], [Pos2; 4]) {
+        let p01 = Self::lerp_pos2(p0, p1, 0.5);
+        let p12 = Self::lerp_pos2(p1, p2, 0.5);
+        let p23 = Self::lerp_pos2(p2, p3, 0.5);
+        let p012 = Self::lerp_pos2(p01, p12, 0.5);
+        let p123 = Self::lerp_pos2(p12, p23, 0.5);
+        let p0123 = Self::lerp_pos2(p012, p123, 0.5);
+        ([p0, p01, p012, p0123], [p0123, p123, p23, p3])
+    }
+
+    // Recursively subdivides a cubic Bezier down to `depth` levels, appending
+    // the flattened points (minus the starting point) to `out`.
+    fn subdivide_bezier(p0: Pos2, p1: Pos2, p2: Pos2, p3: Pos2, depth: u32, out: &mut Vec<Pos2>) {
+        if depth == 0 {
+            out.push(p3);
+            return;
+        }
+        let (left, right) = Self::split_bezier(p0, p1, p2, p3);
+        Self::subdivide_bezier(left[0], left[1], left[2], left[3], depth - 1, out);
+        Self::subdivide_bezier(right[0], right[1], right[2], right[3], depth - 1, out);
+    }
+
+    // Builds the flattened point list for a prerequisite link between `from`
+    // and `to`, bowing the curve outward along the radial normal so links
+    // read clearly instead of cutting straight through the center.
+    fn bezier_link_points(from: Pos2, to: Pos2, center: Pos2) -> Vec<Pos2> {
+        let mid = Self::lerp_pos2(from, to, 0.5);
+        let to_center = mid - center;
+        // `mid` can land exactly on `center` (e.g. two same-radius, opposite
+        // perks), which would normalize to NaN. Fall back to the edge's own
+        // perpendicular so the link still bows instead of degenerating.
+        let edge = to - from;
+        let radial = if to_center.length_sq() > 1e-6 {
+            to_center.normalized()
+        } else if edge.length_sq() > 1e-6 {
+            edge.normalized().rot90()
+        } else {
+            egui::Vec2::new(1.0, 0.0)
+        };
+        let bow = from.distance(to) * 0.15;
+        let p1 = Self::lerp_pos2(from, to, 1.0 / 3.0) + radial * bow;
+        let p2 = Self::lerp_pos2(from, to, 2.0 / 3.0) + radial * bow;
+
+        let mut points = vec![from];
+        Self::subdivide_bezier(from, p1, p2, to, BEZIER_SUBDIVISION_DEPTH, &mut points);
+        points
+    }
+}
+
+impl StatApp {
+    // The tree as it was before perks became data-driven: 9 named supernovas
+    // plus 40 Red Giants and 300 Stars scattered across the reachable area.
+    fn hardcoded_perk_config() -> PerkConfig {
+        let named_perks = vec![
             (
                 "Warrior",
                 "Increase area of effect by 30%",
                 ANG_STR_RED,
                 80.0,
+                10.0,
+                vec![],
+            ),
+            (
+                "Ranger",
+                "+ 2 additional projectiles",
+                ANG_DEX_GREEN,
+                80.0,
+                10.0,
+                vec![],
             ),
-            ("Ranger", "+ 2 additional projectiles", ANG_DEX_GREEN, 80.0),
             (
                 "Mage",
                 "Spells chain to 2 additional targets",
                 ANG_INT_BLUE,
                 80.0,
+                10.0,
+                vec![],
             ),
             (
                 "Duelist",
                 "Attack speed scales with STR/DEX",
                 (ANG_STR_RED + ANG_DEX_GREEN) / 2.0,
                 40.0,
+                10.0,
+                vec!["Warrior", "Ranger"],
             ),
             (
                 "Monk",
                 "Unarmed strikes stun enemies",
                 (ANG_STR_RED + ANG_DEX_GREEN) / 2.0,
                 55.0,
+                10.0,
+                vec!["Warrior", "Ranger"],
             ),
             (
                 "Ranger-Mage",
                 "Arrows deal 5% more elemental damage",
                 (ANG_DEX_GREEN + (ANG_INT_BLUE + 2.0 * PI)) / 2.0,
                 40.0,
+                10.0,
+                vec!["Ranger", "Mage"],
             ),
             (
                 "Arcane Trickster",
                 "Teleport on crit",
                 (ANG_DEX_GREEN + (ANG_INT_BLUE + 2.0 * PI)) / 2.0,
                 55.0,
+                10.0,
+                vec!["Ranger", "Mage"],
             ),
             (
                 "Battlemage",
                 "Gain Energy Shield based on INT",
                 (ANG_INT_BLUE + ANG_STR_RED) / 2.0,
                 40.0,
+                10.0,
+                vec!["Mage", "Warrior"],
             ),
             (
                 "Paladin",
                 "Heal allies on hit",
                 (ANG_INT_BLUE + ANG_STR_RED) / 2.0,
                 55.0,
+                10.0,
+                vec!["Mage", "Warrior"],
             ),
-        ];
+        ]
+        .into_iter()
+        .map(|(name, desc, angle, radius_val, cost, requires)| NamedPerk {
+            name: name.to_string(),
+            description: desc.to_string(),
+            angle_deg: angle.to_degrees(),
+            radius_val,
+            cost,
+            requires: requires.into_iter().map(str::to_string).collect(),
+        })
+        .collect();
+
+        PerkConfig {
+            tiers: vec![
+                PerkTier {
+                    name_prefix: "Red Giant".to_string(),
+                    count: 40,
+                    cost: 5.0,
+                    min_r_percent: 0.4,
+                },
+                PerkTier {
+                    name_prefix: "Star".to_string(),
+                    count: 300,
+                    cost: 2.0,
+                    min_r_percent: 0.2,
+                },
+            ],
+            named_perks,
+        }
+    }
+
+    // Expand a `PerkConfig` into concrete `PerkPoint`s: named perks are placed
+    // as-is, tiers are scattered randomly inside the reachable area the same
+    // way the original hardcoded generation did.
+    fn build_perks_from_config(config: &PerkConfig, rng: &mut impl Rng) -> Vec<PerkPoint> {
+        let mut perks = Vec::new();
 
-        for (name, desc, angle, rad) in fixed_supernovas {
+        for named in &config.named_perks {
             perks.push(PerkPoint {
-                name: name.to_string(),
-                description: desc.to_string(),
-                angle,
-                radius_val: rad,
-                cost: 10.0,
+                name: named.name.clone(),
+                description: named.description.clone(),
+                angle: named.angle_deg.to_radians(),
+                radius_val: named.radius_val,
+                cost: named.cost,
+                wanted: false,
+                requires: Vec::new(),
             });
         }
 
-        // --- RANDOM GENERATION HELPERS ---
         // We define the sectors to pick from
         // (Start Angle, End Angle, V1_is_Start?)
         let sectors = [
@@ -133,16 +489,7 @@ impl Default for StatApp {
             let angle = start_ang + t * (end_ang - start_ang);
 
             // 4. Calculate the MAX POSSIBLE Radius at this angle given the 120 point cap.
-            // We have 120 points. Min stat is 10. So we have 90 points to distribute between V1 and V2.
-            // At t=0 (Axis), V1=100, V2=10.
-            // At t=0.5 (Midpoint), V1=55, V2=55.
-            // At t=1 (Next Axis), V1=10, V2=100.
-            // We approximate the boundary distribution linearly based on 't':
-            let max_v1 = 100.0 - (90.0 * t);
-            let max_v2 = 10.0 + (90.0 * t);
-
-            // Calculate the physical radius limit using the elliptical formula
-            let max_radius_limit = StatApp::calculate_ellipse_radius(max_v1, max_v2, t);
+            let max_radius_limit = StatApp::max_reachable_radius_at_angle(angle);
 
             // 5. Generate a random radius *inside* this limit
             // We ensure it's not too close to the center (min_r_percent)
@@ -154,19 +501,49 @@ impl Default for StatApp {
                 angle,
                 radius_val: radius,
                 cost,
+                wanted: false,
+                requires: Vec::new(),
             });
         };
 
-        // --- B. Generate 40 RED GIANTS (Cost 5.0) ---
-        for i in 0..40 {
-            generate_safe_point(format!("Red Giant {}", i + 1), 5.0, 0.4);
+        for tier in &config.tiers {
+            for i in 0..tier.count {
+                generate_safe_point(format!("{} {}", tier.name_prefix, i + 1), tier.cost, tier.min_r_percent);
+            }
         }
 
-        // --- C. Generate 300 STARS (Cost 2.0) ---
-        for i in 0..300 {
-            generate_safe_point(format!("Star {}", i + 1), 2.0, 0.2);
+        // Resolve named-perk prerequisites (given as names in the config) to
+        // indices into this vec, now that every perk has a final position.
+        let name_to_index: std::collections::HashMap<String, usize> = perks
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (p.name.clone(), i))
+            .collect();
+        for (i, named) in config.named_perks.iter().enumerate() {
+            perks[i].requires = named
+                .requires
+                .iter()
+                .filter_map(|req_name| name_to_index.get(req_name).copied())
+                .collect();
         }
 
+        perks
+    }
+}
+
+impl Default for StatApp {
+    fn default() -> Self {
+        let mut rng = rand::thread_rng();
+        let config_path = Path::new(config::CONFIG_PATH);
+        let (config, config_error) = match PerkConfig::load_from_file(config_path) {
+            Ok(Some(config)) => (config, None),
+            Ok(None) => (Self::hardcoded_perk_config(), None),
+            Err(err) => (Self::hardcoded_perk_config(), Some(err)),
+        };
+        let mut perks = Self::build_perks_from_config(&config, &mut rng);
+        Self::relax_generated_perks(&mut perks, config.named_perks.len());
+        let config_watch = PerkConfigWatcher::spawn(config_path);
+
         Self {
             strength: MIN_STAT_VAL,
             intelligence: MIN_STAT_VAL,
@@ -174,6 +551,13 @@ impl Default for StatApp {
             zoom: 1.0,
             offset: egui::Vec2::ZERO,
             perks,
+            optimizer_log: Vec::new(),
+            config_watch,
+            ring_str_anim: 0.0,
+            ring_dex_anim: 0.0,
+            ring_int_anim: 0.0,
+            build_code_status: None,
+            config_error,
         }
     }
 }
@@ -207,37 +591,341 @@ impl StatApp {
     }
 
     fn get_current_radius_at_angle(&self, angle_rad: f32) -> f32 {
-        let angle_deg = angle_rad.to_degrees().rem_euclid(360.0);
+        Self::radius_at_angle_for_stats(self.strength, self.dexterity, self.intelligence, angle_rad)
+    }
 
-        let (v1, v2, t_sector) = if (45.0..135.0).contains(&angle_deg) {
-            let t = (angle_rad - ANG_DEX_GREEN) / (ANG_STR_RED - ANG_DEX_GREEN);
-            (self.dexterity, self.strength, t)
-        } else if (135.0..270.0).contains(&angle_deg) {
-            let t = (angle_rad - ANG_STR_RED) / (ANG_INT_BLUE - ANG_STR_RED);
-            (self.strength, self.intelligence, t)
-        } else {
-            let start = ANG_INT_BLUE;
-            let end = ANG_DEX_GREEN + 2.0 * PI;
-            let curr = if angle_rad < ANG_INT_BLUE {
-                angle_rad + 2.0 * PI
-            } else {
-                angle_rad
-            };
-            let t = (curr - start) / (end - start);
-            (self.intelligence, self.dexterity, t)
+    // One thick arc of the HUD ring, sweeping clockwise from `start_frac` to
+    // `end_frac` (both 0..1 of a full turn, 0 = straight up).
+    fn draw_ring_arc(
+        painter: &egui::Painter,
+        center: Pos2,
+        radius: f32,
+        start_frac: f32,
+        end_frac: f32,
+        color: Color32,
+    ) {
+        if end_frac <= start_frac {
+            return;
+        }
+        let segments = 48;
+        let points: Vec<Pos2> = (0..=segments)
+            .map(|i| {
+                let frac = start_frac + (end_frac - start_frac) * (i as f32 / segments as f32);
+                let ang = -PI / 2.0 + frac * 2.0 * PI;
+                Pos2::new(center.x + radius * ang.cos(), center.y + radius * ang.sin())
+            })
+            .collect();
+        painter.add(Shape::line(points, Stroke::new(RING_THICKNESS, color)));
+    }
+
+    // Draws the STR/DEX/INT budget ring at a fixed screen position/radius so
+    // it stays put while the tree view is panned and zoomed.
+    fn draw_budget_ring(&self, painter: &egui::Painter, screen_center: Pos2) {
+        let str_end = self.ring_str_anim.clamp(0.0, 1.0);
+        let dex_end = (str_end + self.ring_dex_anim).clamp(0.0, 1.0);
+        let int_end = (dex_end + self.ring_int_anim).clamp(0.0, 1.0);
+
+        Self::draw_ring_arc(
+            painter,
+            screen_center,
+            RING_SCREEN_RADIUS,
+            int_end,
+            1.0,
+            Color32::from_gray(90),
+        );
+        Self::draw_ring_arc(
+            painter,
+            screen_center,
+            RING_SCREEN_RADIUS,
+            0.0,
+            str_end,
+            Color32::from_rgb(200, 50, 50),
+        );
+        Self::draw_ring_arc(
+            painter,
+            screen_center,
+            RING_SCREEN_RADIUS,
+            str_end,
+            dex_end,
+            Color32::from_rgb(50, 200, 50),
+        );
+        Self::draw_ring_arc(
+            painter,
+            screen_center,
+            RING_SCREEN_RADIUS,
+            dex_end,
+            int_end,
+            Color32::from_rgb(50, 50, 200),
+        );
+    }
+
+    // Gaussian noise via Box-Muller, used to mutate GA genomes
+    fn gaussian_noise(rng: &mut impl Rng, std: f32) -> f32 {
+        let u1: f32 = rng.gen_range(1e-6..1.0);
+        let u2: f32 = rng.gen_range(0.0..1.0);
+        let z0 = (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos();
+        z0 * std
+    }
+
+    // Clamp a [str, dex, int] genome to the per-stat range, then scale it down
+    // proportionally if the total exceeds the point cap.
+    fn clamp_and_project(genome: [f32; 3]) -> [f32; 3] {
+        let mut g = [
+            genome[0].clamp(MIN_STAT_VAL, MAX_STAT_VAL),
+            genome[1].clamp(MIN_STAT_VAL, MAX_STAT_VAL),
+            genome[2].clamp(MIN_STAT_VAL, MAX_STAT_VAL),
+        ];
+        let sum: f32 = g.iter().sum();
+        if sum > MAX_TOTAL_POINTS {
+            let scale = MAX_TOTAL_POINTS / sum;
+            for v in &mut g {
+                *v *= scale;
+            }
+        }
+        g
+    }
+
+    // Sum the cost of every wanted perk that a [str, dex, int] genome would
+    // unlock, honoring prerequisite chains. Mirrors the unlock test used when
+    // drawing perks in `update()`.
+    fn genome_fitness(genome: [f32; 3], perks: &[PerkPoint], wanted_indices: &[usize]) -> f32 {
+        let mut cache = vec![None; perks.len()];
+        wanted_indices
+            .iter()
+            .filter(|&&idx| Self::is_unlocked_at(perks, idx, genome[0], genome[1], genome[2], &mut cache))
+            .map(|&idx| perks[idx].cost)
+            .sum()
+    }
+
+    // Search for the stat allocation that unlocks the most value among perks
+    // marked "wanted", via a small genetic algorithm over [str, dex, int] genomes.
+    fn optimize_build(&mut self) {
+        let wanted_indices: Vec<usize> = self
+            .perks
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.wanted)
+            .map(|(i, _)| i)
+            .collect();
+        self.optimizer_log.clear();
+        if wanted_indices.is_empty() {
+            return;
+        }
+
+        let mut rng = rand::thread_rng();
+        let mut population: Vec<[f32; 3]> = (0..GA_POPULATION)
+            .map(|_| {
+                Self::clamp_and_project([
+                    rng.gen_range(MIN_STAT_VAL..=MAX_STAT_VAL),
+                    rng.gen_range(MIN_STAT_VAL..=MAX_STAT_VAL),
+                    rng.gen_range(MIN_STAT_VAL..=MAX_STAT_VAL),
+                ])
+            })
+            .collect();
+
+        let elite_count = ((GA_POPULATION as f32 * GA_ELITE_FRACTION) as usize).max(1);
+        let mut best_genome = population[0];
+        let mut best_fitness = f32::MIN;
+
+        for generation in 0..GA_GENERATIONS {
+            let mut scored: Vec<(f32, [f32; 3])> = population
+                .iter()
+                .map(|&g| (Self::genome_fitness(g, &self.perks, &wanted_indices), g))
+                .collect();
+            scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+            let fitnesses: Vec<f32> = scored.iter().map(|(f, _)| *f).collect();
+            self.optimizer_log
+                .push(GenerationStats::from_sorted_desc(generation, &fitnesses));
+
+            if scored[0].0 > best_fitness {
+                best_fitness = scored[0].0;
+                best_genome = scored[0].1;
+            }
+
+            let elites: Vec<[f32; 3]> = scored.iter().take(elite_count).map(|(_, g)| *g).collect();
+
+            let mut next_gen = elites.clone();
+            while next_gen.len() < GA_POPULATION {
+                let parent_a = elites[rng.gen_range(0..elites.len())];
+                let parent_b = elites[rng.gen_range(0..elites.len())];
+                let mut child = [0.0; 3];
+                for i in 0..3 {
+                    child[i] = if rng.gen_bool(0.5) {
+                        (parent_a[i] + parent_b[i]) / 2.0
+                    } else if rng.gen_bool(0.5) {
+                        parent_a[i]
+                    } else {
+                        parent_b[i]
+                    };
+                    child[i] += Self::gaussian_noise(&mut rng, GA_MUTATION_STD);
+                }
+                next_gen.push(Self::clamp_and_project(child));
+            }
+            population = next_gen;
+        }
+
+        self.strength = best_genome[0];
+        self.dexterity = best_genome[1];
+        self.intelligence = best_genome[2];
+    }
+
+    // Encode the current build (stats, view, wanted perks) as a base64 string
+    // and put it on the clipboard.
+    fn export_build_code(&mut self) {
+        let saved = SavedBuild {
+            strength: self.strength,
+            dexterity: self.dexterity,
+            intelligence: self.intelligence,
+            zoom: self.zoom,
+            offset_x: self.offset.x,
+            offset_y: self.offset.y,
+            wanted: self
+                .perks
+                .iter()
+                .filter(|p| p.wanted)
+                .map(|p| p.name.clone())
+                .collect(),
         };
 
-        // Use the shared static helper
-        Self::calculate_ellipse_radius(v1, v2, t_sector)
+        self.build_code_status = Some(match bincode::serialize(&saved) {
+            Ok(bytes) => {
+                let code = base64::engine::general_purpose::STANDARD.encode(bytes);
+                match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(code)) {
+                    Ok(()) => ("Build copied to clipboard".to_string(), false),
+                    Err(err) => (format!("Couldn't reach the clipboard: {err}"), true),
+                }
+            }
+            Err(err) => (format!("Failed to encode build: {err}"), true),
+        });
+    }
+
+    // Read a build code off the clipboard, decode it, validate it against the
+    // current stat bounds and perk set, and apply it if everything checks out.
+    fn import_build_code(&mut self) {
+        let decoded = arboard::Clipboard::new()
+            .and_then(|mut cb| cb.get_text())
+            .map_err(|err| format!("Couldn't read the clipboard: {err}"))
+            .and_then(|code| {
+                base64::engine::general_purpose::STANDARD
+                    .decode(code.trim())
+                    .map_err(|err| format!("Not a valid build code: {err}"))
+            })
+            .and_then(|bytes| {
+                bincode::deserialize::<SavedBuild>(&bytes)
+                    .map_err(|err| format!("Not a valid build code: {err}"))
+            })
+            .and_then(|saved| self.apply_saved_build(saved));
+
+        self.build_code_status = Some(match decoded {
+            Ok(()) => ("Build imported".to_string(), false),
+            Err(err) => (err, true),
+        });
+    }
+
+    // Validates a decoded `SavedBuild` against the current stat bounds and
+    // perk set before applying it, so a stale or hand-edited code can't put
+    // the app in an inconsistent state.
+    fn apply_saved_build(&mut self, saved: SavedBuild) -> Result<(), String> {
+        for (label, value) in [
+            ("strength", saved.strength),
+            ("dexterity", saved.dexterity),
+            ("intelligence", saved.intelligence),
+        ] {
+            if !(MIN_STAT_VAL..=MAX_STAT_VAL).contains(&value) {
+                return Err(format!("Build code has an out-of-range {label} ({value})"));
+            }
+        }
+        if saved.strength + saved.dexterity + saved.intelligence > MAX_TOTAL_POINTS + 0.01 {
+            return Err("Build code spends more than the point cap".to_string());
+        }
+        if !(0.1..=10.0).contains(&saved.zoom) {
+            return Err(format!("Build code has an out-of-range zoom ({})", saved.zoom));
+        }
+        if !saved.offset_x.is_finite() || !saved.offset_y.is_finite() {
+            return Err("Build code has a non-finite pan offset".to_string());
+        }
+
+        let mut wanted = vec![false; self.perks.len()];
+        for name in &saved.wanted {
+            match self.perks.iter().position(|p| &p.name == name) {
+                Some(idx) => wanted[idx] = true,
+                None => return Err(format!("Build code references unknown perk \"{name}\"")),
+            }
+        }
+
+        self.strength = saved.strength;
+        self.dexterity = saved.dexterity;
+        self.intelligence = saved.intelligence;
+        self.zoom = saved.zoom;
+        self.offset = egui::Vec2::new(saved.offset_x, saved.offset_y);
+        for (perk, is_wanted) in self.perks.iter_mut().zip(wanted) {
+            perk.wanted = is_wanted;
+        }
+        Ok(())
     }
 }
 
 impl eframe::App for StatApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        if let Some(watch) = &self.config_watch {
+            let mut changed = false;
+            while watch.rx.try_recv().is_ok() {
+                changed = true;
+            }
+            if changed {
+                match PerkConfig::load_from_file(Path::new(config::CONFIG_PATH)) {
+                    Ok(Some(config)) => {
+                        let wanted_names: std::collections::HashSet<String> = self
+                            .perks
+                            .iter()
+                            .filter(|p| p.wanted)
+                            .map(|p| p.name.clone())
+                            .collect();
+
+                        let mut rng = rand::thread_rng();
+                        let mut perks = Self::build_perks_from_config(&config, &mut rng);
+                        Self::relax_generated_perks(&mut perks, config.named_perks.len());
+                        for perk in &mut perks {
+                            perk.wanted = wanted_names.contains(&perk.name);
+                        }
+                        self.perks = perks;
+                        self.config_error = None;
+                    }
+                    Ok(None) => self.config_error = None,
+                    Err(err) => self.config_error = Some(err),
+                }
+                ctx.request_repaint();
+            }
+        }
+
         let total_points = self.strength + self.intelligence + self.dexterity;
 
+        let ring_targets = [
+            (&mut self.ring_str_anim, self.strength / MAX_TOTAL_POINTS),
+            (&mut self.ring_dex_anim, self.dexterity / MAX_TOTAL_POINTS),
+            (&mut self.ring_int_anim, self.intelligence / MAX_TOTAL_POINTS),
+        ];
+        let mut ring_animating = false;
+        for (current, target) in ring_targets {
+            let diff = target - *current;
+            if diff.abs() > RING_ANIM_EPSILON {
+                *current += diff * RING_ANIM_LERP;
+                ring_animating = true;
+            } else {
+                *current = target;
+            }
+        }
+        if ring_animating {
+            ctx.request_repaint();
+        }
+
         egui::SidePanel::left("controls_panel").show(ctx, |ui| {
             ui.add_space(20.0);
+            if let Some(err) = &self.config_error {
+                ui.colored_label(Color32::from_rgb(220, 80, 80), format!("perks.ron: {err}"));
+                ui.add_space(10.0);
+            }
             ui.heading("Build Stats");
             ui.label(format!("Points: {} / {}", total_points, MAX_TOTAL_POINTS));
             ui.add(egui::ProgressBar::new(total_points / MAX_TOTAL_POINTS).show_percentage());
@@ -270,7 +958,60 @@ impl eframe::App for StatApp {
                 *self = Self::default();
             }
             ui.add_space(20.0);
-            ui.small("Drag to Pan • Scroll to Zoom");
+            ui.small("Drag to Pan • Scroll to Zoom • Click a perk to mark it wanted");
+
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                if ui.button("Export Build").clicked() {
+                    self.export_build_code();
+                }
+                if ui.button("Import Build").clicked() {
+                    self.import_build_code();
+                }
+            });
+            if let Some((message, is_error)) = &self.build_code_status {
+                let color = if *is_error {
+                    Color32::from_rgb(220, 80, 80)
+                } else {
+                    Color32::from_rgb(120, 220, 120)
+                };
+                ui.colored_label(color, message);
+            }
+
+            ui.add_space(10.0);
+            let wanted_count = self.perks.iter().filter(|p| p.wanted).count();
+            ui.add_enabled_ui(wanted_count > 0, |ui| {
+                if ui
+                    .button(format!("Optimize Build ({} wanted)", wanted_count))
+                    .clicked()
+                {
+                    self.optimize_build();
+                }
+            });
+            if !self.optimizer_log.is_empty() {
+                egui::CollapsingHeader::new("Optimizer Convergence")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        egui::Grid::new("optimizer_log_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.strong("Gen");
+                                ui.strong("Max");
+                                ui.strong("Mean");
+                                ui.strong("Median");
+                                ui.strong("Min");
+                                ui.end_row();
+                                for stats in &self.optimizer_log {
+                                    ui.label(stats.generation.to_string());
+                                    ui.label(format!("{:.1}", stats.max));
+                                    ui.label(format!("{:.1}", stats.mean));
+                                    ui.label(format!("{:.1}", stats.median));
+                                    ui.label(format!("{:.1}", stats.min));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            }
 
             ui.add_space(20.0);
             ui.separator();
@@ -363,15 +1104,59 @@ impl eframe::App for StatApp {
             }));
 
             let pointer_pos = ctx.input(|i| i.pointer.hover_pos());
+            let perk_clicked = response.clicked();
+            let (cur_str, cur_dex, cur_int) = (self.strength, self.dexterity, self.intelligence);
 
-            for perk in &self.perks {
-                let r_px = (perk.radius_val / MAX_STAT_VAL) * max_radius;
-                let pos = Pos2::new(
-                    center.x + r_px * perk.angle.cos(),
-                    center.y - r_px * perk.angle.sin(),
-                );
-                let is_unlocked =
-                    perk.radius_val <= self.get_current_radius_at_angle(perk.angle) + 0.5;
+            let positions: Vec<Pos2> = self
+                .perks
+                .iter()
+                .map(|perk| {
+                    let r_px = (perk.radius_val / MAX_STAT_VAL) * max_radius;
+                    Pos2::new(
+                        center.x + r_px * perk.angle.cos(),
+                        center.y - r_px * perk.angle.sin(),
+                    )
+                })
+                .collect();
+
+            let mut unlocked_cache: Vec<Option<bool>> = vec![None; self.perks.len()];
+            for idx in 0..self.perks.len() {
+                Self::is_unlocked_at(&self.perks, idx, cur_str, cur_dex, cur_int, &mut unlocked_cache);
+            }
+            let unlocked: Vec<bool> = unlocked_cache.into_iter().map(|v| v.unwrap_or(false)).collect();
+
+            let unmet_prereq_names: Vec<Vec<String>> = self
+                .perks
+                .iter()
+                .map(|perk| {
+                    perk.requires
+                        .iter()
+                        .filter(|&&req| !unlocked[req])
+                        .map(|&req| self.perks[req].name.clone())
+                        .collect()
+                })
+                .collect();
+
+            // Prerequisite links, drawn before the perk circles so nodes sit on top.
+            for (idx, perk) in self.perks.iter().enumerate() {
+                for &req in &perk.requires {
+                    let edge_satisfied = unlocked[req];
+                    let color = if edge_satisfied {
+                        Color32::from_rgb(0, 220, 220).gamma_multiply(0.8)
+                    } else {
+                        Color32::from_gray(55)
+                    };
+                    let curve = Self::bezier_link_points(positions[req], positions[idx], center);
+                    painter.add(Shape::line(
+                        curve,
+                        Stroke::new(1.5 * self.zoom.clamp(0.5, 2.0), color),
+                    ));
+                }
+            }
+
+            for (idx, perk) in self.perks.iter_mut().enumerate() {
+                let pos = positions[idx];
+                let is_unlocked = unlocked[idx];
 
                 let (col, stroke_col, base_rad) = if is_unlocked {
                     (Color32::YELLOW, Color32::WHITE, 5.0)
@@ -380,12 +1165,24 @@ impl eframe::App for StatApp {
                 };
 
                 let vis_rad = base_rad * self.zoom.clamp(0.5, 2.0);
+
+                if perk_clicked {
+                    if let Some(pointer) = pointer_pos {
+                        if pointer.distance(pos) <= vis_rad.max(10.0) {
+                            perk.wanted = !perk.wanted;
+                        }
+                    }
+                }
+
                 painter.circle(
                     pos,
                     vis_rad,
                     col,
                     Stroke::new(perk.cost * self.zoom.clamp(0.5, 2.0), stroke_col),
                 );
+                if perk.wanted {
+                    painter.circle_stroke(pos, vis_rad + 3.0, Stroke::new(1.5, Color32::from_rgb(0, 220, 220)));
+                }
 
                 if perk.cost >= 10.0 {
                     painter.text(
@@ -469,9 +1266,26 @@ impl eframe::App for StatApp {
                         if !is_unlocked {
                             ui.label(egui::RichText::new("LOCKED").color(Color32::RED).small());
                         }
+                        let unmet = &unmet_prereq_names[idx];
+                        if !unmet.is_empty() {
+                            ui.label(
+                                egui::RichText::new(format!("Requires: {}", unmet.join(", ")))
+                                    .color(Color32::from_gray(180))
+                                    .small(),
+                            );
+                        }
+                        if perk.wanted {
+                            ui.label(
+                                egui::RichText::new("WANTED (click to unmark)")
+                                    .color(Color32::from_rgb(0, 220, 220))
+                                    .small(),
+                            );
+                        }
                     });
                 }
             }
+
+            self.draw_budget_ring(&painter, response.rect.center());
         });
     }
 }